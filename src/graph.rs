@@ -1,11 +1,18 @@
 use std::{
-    collections::HashMap,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
     intrinsics::transmute,
-    sync::atomic::{AtomicUsize, Ordering},
+    ops::Add,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
     time::Instant,
 };
 
-use rayon::iter::{IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator};
+use rayon::iter::{
+    IntoParallelIterator, IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelIterator,
+};
 
 use crate::{
     input::{Direction, DotGraph, EdgeList},
@@ -39,6 +46,18 @@ impl CSR {
         let to = self.offsets[node + 1];
         &self.targets[from..to]
     }
+
+    fn has_target(&self, node: usize, target: usize) -> bool {
+        let neighbors = self.neighbors(node);
+
+        // binary search pays off once the list is long enough to amortize its
+        // branch mispredictions; below that, a branch-predictable linear scan wins
+        if neighbors.len() > 32 {
+            neighbors.binary_search(&target).is_ok()
+        } else {
+            neighbors.contains(&target)
+        }
+    }
 }
 
 impl From<(&EdgeList, usize, Direction)> for CSR {
@@ -178,6 +197,161 @@ impl DirectedGraph for DirectedCSRGraph {
     fn in_neighbors(&self, node: usize) -> &[usize] {
         self.in_edges.neighbors(node)
     }
+
+    fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.out_edges.has_target(source, target)
+    }
+}
+
+impl DirectedCSRGraph {
+    pub fn scc(&self) -> Vec<usize> {
+        let component = (0..self.node_count)
+            .map(|_| AtomicUsize::new(usize::MAX))
+            .collect::<Vec<_>>();
+        let next_id = AtomicUsize::new(0);
+
+        scc_divide_all(self, (0..self.node_count).collect(), &component, &next_id);
+
+        component.into_iter().map(AtomicUsize::into_inner).collect()
+    }
+}
+
+// drives the forward-backward divide-and-conquer from an explicit worklist instead of
+// recursive calls, so the Rust call stack stays flat no matter how many times the graph
+// has to be split (a long chain of bridged 2-node cycles splits once per pair otherwise).
+// Each worker pulls a partition off the shared stack, splits it, and pushes back whatever
+// still needs dividing; rayon's scope supplies the parallelism across workers.
+fn scc_divide_all(
+    graph: &DirectedCSRGraph,
+    nodes: Vec<usize>,
+    component: &[AtomicUsize],
+    next_id: &AtomicUsize,
+) {
+    if nodes.is_empty() {
+        return;
+    }
+
+    let stack = Mutex::new(vec![nodes]);
+    // counts partitions that are either sitting on the stack or being divided right now;
+    // reaching zero is the only safe signal that there is no more work anywhere
+    let pending = AtomicUsize::new(1);
+
+    rayon::scope(|scope| {
+        for _ in 0..rayon::current_num_threads().max(1) {
+            scope.spawn(|_| loop {
+                let next = stack.lock().unwrap().pop();
+
+                let nodes = match next {
+                    Some(nodes) => nodes,
+                    None => {
+                        if pending.load(Ordering::SeqCst) == 0 {
+                            return;
+                        }
+                        std::thread::yield_now();
+                        continue;
+                    }
+                };
+
+                let partitions = scc_divide_step(graph, nodes, component, next_id);
+
+                // grow `pending` by the new partitions before shrinking it by the one we
+                // just finished, so it can never be observed as zero while work remains
+                pending.fetch_add(partitions.len(), Ordering::SeqCst);
+                if !partitions.is_empty() {
+                    stack.lock().unwrap().extend(partitions);
+                }
+                pending.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+}
+
+// trims singleton SCCs out of `nodes`, splits what remains around a pivot's
+// descendant/predecessor sets, and returns the (at most three) partitions that
+// still need further division.
+fn scc_divide_step(
+    graph: &DirectedCSRGraph,
+    nodes: Vec<usize>,
+    component: &[AtomicUsize],
+    next_id: &AtomicUsize,
+) -> Vec<Vec<usize>> {
+    let mut active: HashSet<usize> = nodes.into_iter().collect();
+
+    loop {
+        let trimmed: Vec<usize> = active
+            .iter()
+            .copied()
+            .filter(|&node| {
+                let has_in = graph.in_neighbors(node).iter().any(|n| active.contains(n));
+                let has_out = graph.out_neighbors(node).iter().any(|n| active.contains(n));
+                !has_in || !has_out
+            })
+            .collect();
+
+        if trimmed.is_empty() {
+            break;
+        }
+
+        for node in trimmed {
+            active.remove(&node);
+            component[node].store(next_id.fetch_add(1, Ordering::SeqCst), Ordering::SeqCst);
+        }
+    }
+
+    if active.is_empty() {
+        return Vec::new();
+    }
+
+    let pivot = *active.iter().next().unwrap();
+    let descendants = bfs_within(graph, pivot, &active, true);
+    let predecessors = bfs_within(graph, pivot, &active, false);
+
+    let scc_id = next_id.fetch_add(1, Ordering::SeqCst);
+    let scc_nodes: HashSet<usize> = descendants.intersection(&predecessors).copied().collect();
+    for &node in &scc_nodes {
+        component[node].store(scc_id, Ordering::SeqCst);
+    }
+
+    let descendants_only: Vec<usize> = descendants.difference(&scc_nodes).copied().collect();
+    let predecessors_only: Vec<usize> = predecessors.difference(&scc_nodes).copied().collect();
+    let remainder: Vec<usize> = active
+        .iter()
+        .copied()
+        .filter(|node| !descendants.contains(node) && !predecessors.contains(node))
+        .collect();
+
+    [descendants_only, predecessors_only, remainder]
+        .into_iter()
+        .filter(|partition| !partition.is_empty())
+        .collect()
+}
+
+fn bfs_within(
+    graph: &DirectedCSRGraph,
+    start: usize,
+    active: &HashSet<usize>,
+    forward: bool,
+) -> HashSet<usize> {
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(start);
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        let neighbors = if forward {
+            graph.out_neighbors(node)
+        } else {
+            graph.in_neighbors(node)
+        };
+
+        for &next in neighbors {
+            if active.contains(&next) && visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+
+    visited
 }
 
 impl From<EdgeList> for DirectedCSRGraph {
@@ -190,6 +364,50 @@ impl From<EdgeList> for DirectedCSRGraph {
     }
 }
 
+impl DirectedCSRGraph {
+    pub fn pagerank(&self, damping: f64, max_iters: usize, tol: f64) -> Vec<f64> {
+        let node_count = self.node_count;
+        let mut ranks = vec![1.0 / node_count as f64; node_count];
+
+        for _ in 0..max_iters {
+            let dangling_sum: f64 = (0..node_count)
+                .into_par_iter()
+                .filter(|&node| self.out_degree(node) == 0)
+                .map(|node| ranks[node])
+                .sum();
+
+            let base =
+                (1.0 - damping) / node_count as f64 + damping * dangling_sum / node_count as f64;
+
+            let new_ranks: Vec<f64> = (0..node_count)
+                .into_par_iter()
+                .map(|node| {
+                    let incoming: f64 = self
+                        .in_neighbors(node)
+                        .iter()
+                        .map(|&source| ranks[source] / self.out_degree(source) as f64)
+                        .sum();
+                    base + damping * incoming
+                })
+                .collect();
+
+            let diff: f64 = new_ranks
+                .iter()
+                .zip(ranks.iter())
+                .map(|(new, old)| (new - old).abs())
+                .sum();
+
+            ranks = new_ranks;
+
+            if diff < tol {
+                break;
+            }
+        }
+
+        ranks
+    }
+}
+
 pub struct UndirectedCSRGraph {
     node_count: usize,
     edge_count: usize,
@@ -224,6 +442,10 @@ impl UndirectedGraph for UndirectedCSRGraph {
     fn neighbors(&self, node: usize) -> &[usize] {
         self.edges.neighbors(node)
     }
+
+    fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.edges.has_target(source, target)
+    }
 }
 
 impl From<EdgeList> for UndirectedCSRGraph {
@@ -235,11 +457,51 @@ impl From<EdgeList> for UndirectedCSRGraph {
     }
 }
 
+impl UndirectedCSRGraph {
+    pub fn triangle_count(&self) -> usize {
+        self.per_node_triangles().iter().sum()
+    }
+
+    pub fn per_node_triangles(&self) -> Vec<usize> {
+        (0..self.node_count)
+            .into_par_iter()
+            .map(|u| {
+                self.neighbors(u)
+                    .iter()
+                    .filter(|&&v| v > u)
+                    .map(|&v| sorted_intersection_above(self.neighbors(u), self.neighbors(v), v))
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+// counts the shared elements of two sorted slices that are strictly greater than `above`,
+// ensuring each triangle u-v-w is only counted once (at its smallest vertex)
+fn sorted_intersection_above(a: &[usize], b: &[usize], above: usize) -> usize {
+    let a = &a[a.partition_point(|&x| x <= above)..];
+    let b = &b[b.partition_point(|&x| x <= above)..];
+
+    let (mut i, mut j, mut count) = (0, 0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                count += 1;
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    count
+}
+
 pub struct NodeLabeledCSRGraph<G> {
     graph: G,
+    labels: Box<[usize]>,
     label_index: Box<[usize]>,
     label_index_offsets: Box<[usize]>,
-    max_degree: usize,
     max_label: usize,
     max_label_frequency: usize,
     label_frequency: HashMap<usize, usize>,
@@ -284,10 +546,855 @@ impl<G: UndirectedGraph> UndirectedGraph for NodeLabeledCSRGraph<G> {
     fn neighbors(&self, node: usize) -> &[usize] {
         self.graph.neighbors(node)
     }
+
+    // delegate rather than re-deriving the binary-search cutoff: `G` is only guaranteed to be
+    // sorted when it actually is a CSR, and `G::has_edge` already knows which it is
+    fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.graph.has_edge(source, target)
+    }
+}
+
+impl<G: From<EdgeList> + UndirectedGraph> From<DotGraph> for NodeLabeledCSRGraph<G> {
+    fn from(dot_graph: DotGraph) -> Self {
+        let labels = dot_graph.labels().to_vec();
+        let node_count = labels.len();
+        let edge_list = dot_graph.into_edge_list();
+        let graph = G::from(edge_list);
+
+        // group nodes by label using the same offsets + flat index layout CSR uses for adjacency
+        let max_label = labels.iter().copied().max().unwrap_or(0);
+
+        let mut label_frequency = HashMap::new();
+        for &label in &labels {
+            *label_frequency.entry(label).or_insert(0) += 1;
+        }
+
+        let mut label_index_offsets = vec![0usize; max_label + 2];
+        for label in 0..=max_label {
+            let count = label_frequency.get(&label).copied().unwrap_or(0);
+            label_index_offsets[label + 1] = label_index_offsets[label] + count;
+        }
+
+        let mut cursor = label_index_offsets.clone();
+        let mut label_index = vec![0usize; node_count];
+        for (node, &label) in labels.iter().enumerate() {
+            label_index[cursor[label]] = node;
+            cursor[label] += 1;
+        }
+
+        let max_label_frequency = label_frequency.values().copied().max().unwrap_or(0);
+
+        let neighbor_label_frequencies = (0..node_count)
+            .map(|node| {
+                let mut frequency = HashMap::new();
+                for &neighbor in graph.neighbors(node) {
+                    *frequency.entry(labels[neighbor]).or_insert(0) += 1;
+                }
+                frequency
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        NodeLabeledCSRGraph {
+            graph,
+            labels: labels.into_boxed_slice(),
+            label_index: label_index.into_boxed_slice(),
+            label_index_offsets: label_index_offsets.into_boxed_slice(),
+            max_label,
+            max_label_frequency,
+            label_frequency,
+            neighbor_label_frequencies: Some(neighbor_label_frequencies),
+        }
+    }
+}
+
+impl<G> NodeLabeledCSRGraph<G> {
+    pub fn nodes_with_label(&self, label: usize) -> &[usize] {
+        let from = self.label_index_offsets[label];
+        let to = self.label_index_offsets[label + 1];
+        &self.label_index[from..to]
+    }
+
+    pub fn label_of(&self, node: usize) -> usize {
+        self.labels[node]
+    }
+
+    pub fn neighbor_label_frequency(&self, node: usize, label: usize) -> usize {
+        self.neighbor_label_frequencies
+            .as_ref()
+            .and_then(|frequencies| frequencies[node].get(&label))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl<G: UndirectedGraph> NodeLabeledCSRGraph<G> {
+    pub fn match_subgraph(&self, query: &NodeLabeledCSRGraph<G>) -> Vec<Vec<usize>> {
+        let query_node_count = query.node_count();
+        if query_node_count == 0 {
+            return Vec::new();
+        }
+
+        // visit one connected component at a time, rooting each at its rarest query label
+        // to keep the initial candidate set small; a disconnected query must still get
+        // every vertex ordered, or `extend_match` would map it with leftover placeholders
+        let mut order = Vec::with_capacity(query_node_count);
+        let mut visited = vec![false; query_node_count];
+
+        while order.len() < query_node_count {
+            let root = (0..query_node_count)
+                .filter(|&node| !visited[node])
+                .min_by_key(|&node| query.label_frequency(query.label_of(node)))
+                .unwrap();
+
+            visited[root] = true;
+            order.push(root);
+
+            let mut queue = VecDeque::from([root]);
+            while let Some(node) = queue.pop_front() {
+                for &neighbor in query.neighbors(node) {
+                    if !visited[neighbor] {
+                        visited[neighbor] = true;
+                        order.push(neighbor);
+                        queue.push_back(neighbor);
+                    }
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut mapping = vec![usize::MAX; query_node_count];
+        let mut used = HashSet::new();
+        self.extend_match(query, &order, 0, &mut mapping, &mut used, &mut results);
+        results
+    }
+
+    fn label_frequency(&self, label: usize) -> usize {
+        self.label_frequency.get(&label).copied().unwrap_or(0)
+    }
+
+    fn extend_match(
+        &self,
+        query: &NodeLabeledCSRGraph<G>,
+        order: &[usize],
+        pos: usize,
+        mapping: &mut [usize],
+        used: &mut HashSet<usize>,
+        results: &mut Vec<Vec<usize>>,
+    ) {
+        if pos == order.len() {
+            results.push(mapping.to_vec());
+            return;
+        }
+
+        let query_node = order[pos];
+        let label = query.label_of(query_node);
+        let mapped_neighbor = order[..pos]
+            .iter()
+            .find(|&&prev| query.has_edge(query_node, prev));
+
+        let candidates: Vec<usize> = match mapped_neighbor {
+            Some(&prev) => self.neighbors(mapping[prev]).to_vec(),
+            None => self.nodes_with_label(label).to_vec(),
+        };
+
+        for candidate in candidates {
+            if used.contains(&candidate) || self.label_of(candidate) != label {
+                continue;
+            }
+            if self.degree(candidate) < query.degree(query_node) {
+                continue;
+            }
+            if query.neighbors(query_node).iter().any(|&neighbor| {
+                let neighbor_label = query.label_of(neighbor);
+                self.neighbor_label_frequency(candidate, neighbor_label)
+                    < query.neighbor_label_frequency(query_node, neighbor_label)
+            }) {
+                continue;
+            }
+
+            // non-induced match: every query edge must be present in the target, but the
+            // target is free to have extra edges the query doesn't (e.g. to an already-mapped
+            // vertex from an unrelated query component)
+            let structurally_consistent = order[..pos].iter().all(|&prev| {
+                !query.has_edge(query_node, prev) || self.has_edge(candidate, mapping[prev])
+            });
+            if !structurally_consistent {
+                continue;
+            }
+
+            mapping[query_node] = candidate;
+            used.insert(candidate);
+            self.extend_match(query, order, pos + 1, mapping, used, results);
+            used.remove(&candidate);
+            mapping[query_node] = usize::MAX;
+        }
+    }
+}
+
+pub trait Zero {
+    fn zero() -> Self;
+}
+
+macro_rules! impl_zero {
+    ($($t:ty),*) => {
+        $(impl Zero for $t {
+            fn zero() -> Self {
+                0 as $t
+            }
+        })*
+    };
+}
+
+impl_zero!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+pub struct WeightedEdgeList<W> {
+    edges: Vec<(usize, usize, W)>,
+}
+
+impl<W> WeightedEdgeList<W> {
+    pub fn new(edges: Vec<(usize, usize, W)>) -> Self {
+        Self { edges }
+    }
+
+    fn max_node_id(&self) -> usize {
+        self.edges
+            .iter()
+            .map(|&(source, target, _)| source.max(target))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+impl<W> From<Vec<(usize, usize, W)>> for WeightedEdgeList<W> {
+    fn from(edges: Vec<(usize, usize, W)>) -> Self {
+        Self::new(edges)
+    }
+}
+
+pub struct WeightedCSR<W> {
+    offsets: Box<[usize]>,
+    targets: Box<[usize]>,
+    weights: Box<[W]>,
+}
+
+impl<W: Copy> WeightedCSR<W> {
+    #[inline]
+    fn node_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    #[inline]
+    fn degree(&self, node: usize) -> usize {
+        self.offsets[node + 1] - self.offsets[node]
+    }
+
+    #[inline]
+    fn targets(&self, node: usize) -> &[usize] {
+        let from = self.offsets[node];
+        let to = self.offsets[node + 1];
+        &self.targets[from..to]
+    }
+
+    #[inline]
+    fn weights(&self, node: usize) -> &[W] {
+        let from = self.offsets[node];
+        let to = self.offsets[node + 1];
+        &self.weights[from..to]
+    }
+
+    fn weighted_neighbors(&self, node: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.targets(node)
+            .iter()
+            .copied()
+            .zip(self.weights(node).iter().copied())
+    }
+}
+
+impl<W: Copy + Send> From<(&WeightedEdgeList<W>, usize, Direction)> for WeightedCSR<W> {
+    fn from((edge_list, node_count, direction): (&WeightedEdgeList<W>, usize, Direction)) -> Self {
+        let mut adj: Vec<Vec<(usize, W)>> = (0..node_count).map(|_| Vec::new()).collect();
+
+        for &(source, target, weight) in &edge_list.edges {
+            match direction {
+                Direction::Outgoing => adj[source].push((target, weight)),
+                Direction::Incoming => adj[target].push((source, weight)),
+                Direction::Undirected => {
+                    adj[source].push((target, weight));
+                    adj[target].push((source, weight));
+                }
+            }
+        }
+
+        // match sort_targets's idiom of sorting each node's list on its own thread
+        adj.par_iter_mut()
+            .for_each(|list| list.sort_unstable_by_key(|&(target, _)| target));
+
+        let degrees = adj.iter().map(Vec::len).collect::<Vec<_>>();
+        let offsets = prefix_sum(&degrees);
+
+        let mut targets = Vec::with_capacity(offsets[node_count]);
+        let mut weights = Vec::with_capacity(offsets[node_count]);
+        for list in adj {
+            for (target, weight) in list {
+                targets.push(target);
+                weights.push(weight);
+            }
+        }
+
+        WeightedCSR {
+            offsets: offsets.into_boxed_slice(),
+            targets: targets.into_boxed_slice(),
+            weights: weights.into_boxed_slice(),
+        }
+    }
+}
+
+pub struct WeightedDirectedCSRGraph<W> {
+    node_count: usize,
+    edge_count: usize,
+    out_edges: WeightedCSR<W>,
+    in_edges: WeightedCSR<W>,
+}
+
+impl<W: Copy> WeightedDirectedCSRGraph<W> {
+    pub fn new(out_edges: WeightedCSR<W>, in_edges: WeightedCSR<W>) -> Self {
+        Self {
+            node_count: out_edges.node_count(),
+            edge_count: out_edges.edge_count(),
+            out_edges,
+            in_edges,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn out_degree(&self, node: usize) -> usize {
+        self.out_edges.degree(node)
+    }
+
+    pub fn in_degree(&self, node: usize) -> usize {
+        self.in_edges.degree(node)
+    }
+
+    pub fn weighted_neighbors(&self, node: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.out_edges.weighted_neighbors(node)
+    }
+
+    pub fn weighted_in_neighbors(&self, node: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.in_edges.weighted_neighbors(node)
+    }
+
+    pub fn dijkstra(&self, source: usize) -> Vec<Option<W>>
+    where
+        W: Ord + Add<Output = W> + Zero,
+    {
+        let mut distances = vec![None; self.node_count];
+        distances[source] = Some(W::zero());
+
+        let mut heap = BinaryHeap::new();
+        heap.push(Reverse((W::zero(), source)));
+
+        while let Some(Reverse((dist, node))) = heap.pop() {
+            if matches!(distances[node], Some(best) if dist > best) {
+                continue;
+            }
+
+            for (target, weight) in self.weighted_neighbors(node) {
+                let next = dist + weight;
+                let improves = match distances[target] {
+                    Some(best) => next < best,
+                    None => true,
+                };
+                if improves {
+                    distances[target] = Some(next);
+                    heap.push(Reverse((next, target)));
+                }
+            }
+        }
+
+        distances
+    }
+}
+
+impl<W: Copy + Send> From<WeightedEdgeList<W>> for WeightedDirectedCSRGraph<W> {
+    fn from(edge_list: WeightedEdgeList<W>) -> Self {
+        let node_count = edge_list.max_node_id() + 1;
+        let out_edges = WeightedCSR::from((&edge_list, node_count, Direction::Outgoing));
+        let in_edges = WeightedCSR::from((&edge_list, node_count, Direction::Incoming));
+
+        WeightedDirectedCSRGraph::new(out_edges, in_edges)
+    }
+}
+
+pub struct WeightedUndirectedCSRGraph<W> {
+    node_count: usize,
+    edge_count: usize,
+    edges: WeightedCSR<W>,
+}
+
+impl<W: Copy> WeightedUndirectedCSRGraph<W> {
+    pub fn new(edges: WeightedCSR<W>) -> Self {
+        Self {
+            node_count: edges.node_count(),
+            edge_count: edges.edge_count() / 2,
+            edges,
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn degree(&self, node: usize) -> usize {
+        self.edges.degree(node)
+    }
+
+    pub fn weighted_neighbors(&self, node: usize) -> impl Iterator<Item = (usize, W)> + '_ {
+        self.edges.weighted_neighbors(node)
+    }
+}
+
+impl<W: Copy + Send> From<WeightedEdgeList<W>> for WeightedUndirectedCSRGraph<W> {
+    fn from(edge_list: WeightedEdgeList<W>) -> Self {
+        let node_count = edge_list.max_node_id() + 1;
+        let edges = WeightedCSR::from((&edge_list, node_count, Direction::Undirected));
+
+        WeightedUndirectedCSRGraph::new(edges)
+    }
+}
+
+pub struct EditableGraph {
+    adjacency: Vec<Vec<usize>>,
 }
 
-impl<G: From<EdgeList>> From<DotGraph> for NodeLabeledCSRGraph<G> {
-    fn from(_: DotGraph) -> Self {
-        todo!()
+impl EditableGraph {
+    pub fn new() -> Self {
+        Self {
+            adjacency: Vec::new(),
+        }
+    }
+
+    fn add_node(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    fn remove_last_node(&mut self, node: usize) {
+        assert_eq!(
+            self.adjacency.len() - 1,
+            node,
+            "can only remove the most recently added node"
+        );
+        self.adjacency.pop();
+    }
+
+    fn add_edge(&mut self, source: usize, target: usize) {
+        self.adjacency[source].push(target);
+    }
+
+    fn remove_edge(&mut self, source: usize, target: usize) -> bool {
+        match self.adjacency[source].iter().position(|&t| t == target) {
+            Some(pos) => {
+                self.adjacency[source].remove(pos);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Default for EditableGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub trait Command {
+    // returns whether the command actually changed the graph; a no-op apply (e.g.
+    // removing an edge that isn't there) must not be recorded in history, or undo
+    // would replay an inverse that fabricates state that never existed
+    fn apply(&mut self, graph: &mut EditableGraph) -> bool;
+    fn undo(&self) -> Box<dyn Command>;
+}
+
+#[derive(Default)]
+pub struct AddNode {
+    node: Option<usize>,
+}
+
+impl Command for AddNode {
+    fn apply(&mut self, graph: &mut EditableGraph) -> bool {
+        self.node = Some(graph.add_node());
+        true
+    }
+
+    fn undo(&self) -> Box<dyn Command> {
+        let node = self
+            .node
+            .expect("AddNode must be applied before it can be undone");
+        Box::new(RemoveLastNode { node })
+    }
+}
+
+struct RemoveLastNode {
+    node: usize,
+}
+
+impl Command for RemoveLastNode {
+    fn apply(&mut self, graph: &mut EditableGraph) -> bool {
+        graph.remove_last_node(self.node);
+        true
+    }
+
+    fn undo(&self) -> Box<dyn Command> {
+        Box::new(AddNode {
+            node: Some(self.node),
+        })
+    }
+}
+
+pub struct AddEdge {
+    pub source: usize,
+    pub target: usize,
+}
+
+impl Command for AddEdge {
+    fn apply(&mut self, graph: &mut EditableGraph) -> bool {
+        graph.add_edge(self.source, self.target);
+        true
+    }
+
+    fn undo(&self) -> Box<dyn Command> {
+        Box::new(RemoveEdge {
+            source: self.source,
+            target: self.target,
+        })
+    }
+}
+
+pub struct RemoveEdge {
+    pub source: usize,
+    pub target: usize,
+}
+
+impl Command for RemoveEdge {
+    fn apply(&mut self, graph: &mut EditableGraph) -> bool {
+        graph.remove_edge(self.source, self.target)
+    }
+
+    fn undo(&self) -> Box<dyn Command> {
+        Box::new(AddEdge {
+            source: self.source,
+            target: self.target,
+        })
+    }
+}
+
+type Inverse = Box<dyn Command>;
+
+pub struct CommandHistory {
+    commands: Vec<(Box<dyn Command>, Inverse)>,
+    cursor: usize,
+}
+
+impl CommandHistory {
+    pub fn new() -> Self {
+        Self {
+            commands: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    // returns whether the command was applied and recorded; a no-op apply is
+    // dropped instead of being pushed onto the history
+    pub fn push(&mut self, mut command: Box<dyn Command>, graph: &mut EditableGraph) -> bool {
+        if !command.apply(graph) {
+            return false;
+        }
+        let inverse = command.undo();
+
+        self.commands.truncate(self.cursor);
+        self.commands.push((command, inverse));
+        self.cursor = self.commands.len();
+        true
+    }
+
+    pub fn undo(&mut self, graph: &mut EditableGraph) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        let _ = self.commands[self.cursor].1.apply(graph);
+        true
+    }
+
+    pub fn redo(&mut self, graph: &mut EditableGraph) -> bool {
+        if self.cursor == self.commands.len() {
+            return false;
+        }
+        let _ = self.commands[self.cursor].0.apply(graph);
+        self.cursor += 1;
+        true
+    }
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct GraphEditor {
+    graph: EditableGraph,
+    history: CommandHistory,
+}
+
+impl GraphEditor {
+    pub fn new() -> Self {
+        Self {
+            graph: EditableGraph::new(),
+            history: CommandHistory::new(),
+        }
+    }
+
+    pub fn add_node(&mut self) -> usize {
+        self.history
+            .push(Box::new(AddNode::default()), &mut self.graph);
+        self.graph.adjacency.len() - 1
+    }
+
+    pub fn add_edge(&mut self, source: usize, target: usize) -> bool {
+        self.history
+            .push(Box::new(AddEdge { source, target }), &mut self.graph)
+    }
+
+    pub fn remove_edge(&mut self, source: usize, target: usize) -> bool {
+        self.history
+            .push(Box::new(RemoveEdge { source, target }), &mut self.graph)
+    }
+
+    pub fn undo(&mut self) -> bool {
+        self.history.undo(&mut self.graph)
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.history.redo(&mut self.graph)
+    }
+
+    pub fn freeze(self) -> DirectedCSRGraph {
+        let edges: Vec<(usize, usize)> = self
+            .graph
+            .adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(source, targets)| targets.iter().map(move |&target| (source, target)))
+            .collect();
+
+        DirectedCSRGraph::from(EdgeList::from(edges))
+    }
+}
+
+impl Default for GraphEditor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dijkstra_finds_shortest_paths() {
+        // 0 -4-> 1, 0 -1-> 2, 1 -5-> 3, 2 -2-> 1, 2 -8-> 3
+        let out_edges = WeightedCSR {
+            offsets: vec![0, 2, 3, 5, 5].into_boxed_slice(),
+            targets: vec![1, 2, 3, 1, 3].into_boxed_slice(),
+            weights: vec![4u32, 1, 5, 2, 8].into_boxed_slice(),
+        };
+        let in_edges = WeightedCSR {
+            offsets: vec![0, 0, 0, 0, 0].into_boxed_slice(),
+            targets: Vec::new().into_boxed_slice(),
+            weights: Vec::<u32>::new().into_boxed_slice(),
+        };
+        let graph = WeightedDirectedCSRGraph::new(out_edges, in_edges);
+
+        let distances = graph.dijkstra(0);
+
+        assert_eq!(distances, vec![Some(0), Some(3), Some(1), Some(8)]);
+    }
+
+    #[test]
+    fn pagerank_converges_to_uniform_on_a_symmetric_cycle() {
+        let out_edges = CSR {
+            offsets: vec![0, 1, 2, 3].into_boxed_slice(),
+            targets: vec![1, 2, 0].into_boxed_slice(),
+        };
+        let in_edges = CSR {
+            offsets: vec![0, 1, 2, 3].into_boxed_slice(),
+            targets: vec![2, 0, 1].into_boxed_slice(),
+        };
+        let graph = DirectedCSRGraph::new(out_edges, in_edges);
+
+        let ranks = graph.pagerank(0.85, 100, 1e-9);
+
+        for rank in ranks {
+            assert!((rank - 1.0 / 3.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn has_edge_is_correct_on_both_sides_of_the_binary_search_cutoff() {
+        // node 0 has 39 sorted neighbors (over has_target's binary-search cutoff of 32),
+        // node 41 has 2 (under it); every other node is isolated
+        let mut offsets = vec![39usize; 43];
+        offsets[0] = 0;
+        offsets[42] = 41;
+
+        let mut targets: Vec<usize> = (2..=40).collect();
+        targets.extend([0, 1]);
+
+        let graph = UndirectedCSRGraph::new(CSR {
+            offsets: offsets.into_boxed_slice(),
+            targets: targets.into_boxed_slice(),
+        });
+
+        assert!(graph.has_edge(0, 5));
+        assert!(!graph.has_edge(0, 1));
+        assert!(graph.has_edge(41, 0));
+        assert!(!graph.has_edge(41, 5));
+    }
+
+    #[test]
+    fn triangle_count_counts_one_triangle_and_zero_on_a_path() {
+        let triangle = UndirectedCSRGraph::new(CSR {
+            offsets: vec![0, 2, 4, 6].into_boxed_slice(),
+            targets: vec![1, 2, 0, 2, 0, 1].into_boxed_slice(),
+        });
+        assert_eq!(triangle.triangle_count(), 1);
+
+        let path = UndirectedCSRGraph::new(CSR {
+            offsets: vec![0, 1, 3, 4].into_boxed_slice(),
+            targets: vec![1, 0, 2, 1].into_boxed_slice(),
+        });
+        assert_eq!(path.triangle_count(), 0);
+    }
+
+    #[test]
+    fn scc_groups_a_cycle_and_leaves_an_acyclic_chain_split() {
+        // 0 -> 1 -> 2 -> 0 is a true cycle; 3 -> 4 -> 5 is a chain with no back edge
+        let out_edges = CSR {
+            offsets: vec![0, 1, 2, 3, 4, 5, 5].into_boxed_slice(),
+            targets: vec![1, 2, 0, 4, 5].into_boxed_slice(),
+        };
+        let in_edges = CSR {
+            offsets: vec![0, 1, 2, 3, 3, 4, 5].into_boxed_slice(),
+            targets: vec![2, 0, 1, 3, 4].into_boxed_slice(),
+        };
+        let graph = DirectedCSRGraph::new(out_edges, in_edges);
+
+        let component = graph.scc();
+
+        assert_eq!(component[0], component[1]);
+        assert_eq!(component[1], component[2]);
+        assert_ne!(component[3], component[0]);
+        assert_ne!(component[4], component[0]);
+        assert_ne!(component[5], component[0]);
+        assert_ne!(component[3], component[4]);
+        assert_ne!(component[4], component[5]);
+    }
+
+    #[test]
+    fn match_subgraph_finds_every_labeled_edge_occurrence() {
+        // target: a 0-1-2-3 path labeled A-B-A-B
+        let target_graph = UndirectedCSRGraph::new(CSR {
+            offsets: vec![0, 1, 3, 5, 6].into_boxed_slice(),
+            targets: vec![1, 0, 2, 1, 3, 2].into_boxed_slice(),
+        });
+        let target = NodeLabeledCSRGraph {
+            graph: target_graph,
+            labels: vec![0, 1, 0, 1].into_boxed_slice(),
+            label_index: vec![0, 2, 1, 3].into_boxed_slice(),
+            label_index_offsets: vec![0, 2, 4].into_boxed_slice(),
+            max_label: 1,
+            max_label_frequency: 2,
+            label_frequency: HashMap::from([(0, 2), (1, 2)]),
+            neighbor_label_frequencies: Some(
+                vec![
+                    HashMap::from([(1, 1)]),
+                    HashMap::from([(0, 2)]),
+                    HashMap::from([(1, 2)]),
+                    HashMap::from([(0, 1)]),
+                ]
+                .into_boxed_slice(),
+            ),
+        };
+
+        // query: a single A-B edge
+        let query_graph = UndirectedCSRGraph::new(CSR {
+            offsets: vec![0, 1, 2].into_boxed_slice(),
+            targets: vec![1, 0].into_boxed_slice(),
+        });
+        let query = NodeLabeledCSRGraph {
+            graph: query_graph,
+            labels: vec![0, 1].into_boxed_slice(),
+            label_index: vec![0, 1].into_boxed_slice(),
+            label_index_offsets: vec![0, 1, 2].into_boxed_slice(),
+            max_label: 1,
+            max_label_frequency: 1,
+            label_frequency: HashMap::from([(0, 1), (1, 1)]),
+            neighbor_label_frequencies: Some(
+                vec![HashMap::from([(1, 1)]), HashMap::from([(0, 1)])].into_boxed_slice(),
+            ),
+        };
+
+        let matches = target.match_subgraph(&query);
+
+        assert_eq!(matches, vec![vec![0, 1], vec![2, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn undo_redo_roundtrips_and_a_no_op_remove_is_not_recorded() {
+        let mut editor = GraphEditor::new();
+        let a = editor.add_node();
+        let b = editor.add_node();
+        assert!(editor.add_edge(a, b));
+
+        // removing an edge that was never added is a no-op and must not land in history
+        assert!(!editor.remove_edge(b, a));
+
+        assert_eq!(editor.graph.adjacency, vec![vec![b], vec![]]);
+
+        assert!(editor.undo());
+        assert_eq!(editor.graph.adjacency, vec![vec![], vec![]]);
+
+        // the no-op remove_edge must not have been recorded, so a single undo is
+        // enough to unwind the add_edge; a second undo only unwinds add_node(b)
+        assert!(editor.undo());
+        assert_eq!(editor.graph.adjacency, vec![vec![]]);
+
+        assert!(editor.redo());
+        assert_eq!(editor.graph.adjacency, vec![vec![], vec![]]);
+
+        assert!(editor.redo());
+        assert_eq!(editor.graph.adjacency, vec![vec![b], vec![]]);
+
+        assert!(!editor.redo());
     }
 }
@@ -0,0 +1,33 @@
+pub mod input;
+
+mod graph;
+pub use graph::*;
+
+pub trait Graph {
+    fn node_count(&self) -> usize;
+    fn edge_count(&self) -> usize;
+}
+
+pub trait DirectedGraph: Graph {
+    fn out_degree(&self, node: usize) -> usize;
+    fn out_neighbors(&self, node: usize) -> &[usize];
+    fn in_degree(&self, node: usize) -> usize;
+    fn in_neighbors(&self, node: usize) -> &[usize];
+
+    // default linear scan; DirectedCSRGraph overrides this to binary-search
+    // adjacency lists longer than 32 entries
+    fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.out_neighbors(source).contains(&target)
+    }
+}
+
+pub trait UndirectedGraph: Graph {
+    fn degree(&self, node: usize) -> usize;
+    fn neighbors(&self, node: usize) -> &[usize];
+
+    // default linear scan; UndirectedCSRGraph overrides this to binary-search
+    // adjacency lists longer than 32 entries
+    fn has_edge(&self, source: usize, target: usize) -> bool {
+        self.neighbors(source).contains(&target)
+    }
+}